@@ -0,0 +1,208 @@
+//! A growable, [`BytesMut`]-backed companion to [`BytesStr`].
+
+use crate::BytesStr;
+use bytes::{Bytes, BytesMut};
+use std::fmt;
+use std::ops::Deref;
+use std::str::from_utf8_unchecked;
+
+/// A mutable UTF8 string backed by [`BytesMut`].
+///
+/// `BytesStrMut` lets callers build up a string incrementally and then hand it off to a
+/// [`BytesStr`] with [`freeze`](BytesStrMut::freeze) in O(1), without copying its contents.
+#[derive(Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BytesStrMut {
+    // This must always be valid UTF8
+    bytes: BytesMut,
+}
+
+impl BytesStrMut {
+    /// Creates a new, empty `BytesStrMut`.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            bytes: BytesMut::new(),
+        }
+    }
+
+    /// Creates a new, empty `BytesStrMut` with at least the given capacity.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            bytes: BytesMut::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the number of bytes the buffer can hold without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.bytes.capacity()
+    }
+
+    /// Reserves capacity for at least `additional` more bytes.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.bytes.reserve(additional);
+    }
+
+    /// Appends `s` to the end of the buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytesstr::BytesStrMut;
+    ///
+    /// let mut s = BytesStrMut::new();
+    /// s.push_str("Hello, ");
+    /// s.push_str("World!");
+    ///
+    /// assert_eq!(s, "Hello, World!");
+    /// ```
+    #[inline]
+    pub fn push_str(&mut self, s: &str) {
+        self.bytes.extend_from_slice(s.as_bytes());
+    }
+
+    /// Appends `c` to the end of the buffer.
+    #[inline]
+    pub fn push(&mut self, c: char) {
+        self.bytes
+            .extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+    }
+
+    /// Appends every `&str` yielded by `strs` to the end of the buffer.
+    #[inline]
+    pub fn extend_from_str<'a, I>(&mut self, strs: I)
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        for s in strs {
+            self.push_str(s);
+        }
+    }
+
+    /// Returns a str slice into the internal buffer
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        // Safety:
+        // There is no safe way to construct a BytesStrMut from an invalid UTF8 string
+        unsafe { from_utf8_unchecked(&self.bytes) }
+    }
+
+    /// Converts `self` into a [`BytesStr`] in O(1) by freezing the underlying `BytesMut`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytesstr::BytesStrMut;
+    ///
+    /// let mut s = BytesStrMut::new();
+    /// s.push_str("Test!");
+    ///
+    /// assert_eq!(s.freeze(), "Test!");
+    /// ```
+    #[inline]
+    pub fn freeze(self) -> BytesStr {
+        // Safety: the UTF8 invariant is maintained by every mutating method on BytesStrMut
+        unsafe { BytesStr::from_utf8_bytes_unchecked(self.bytes.freeze()) }
+    }
+}
+
+impl BytesStr {
+    /// Converts `self` into a [`BytesStrMut`], reusing the buffer in O(1) if it isn't shared
+    /// with another `BytesStr`/`Bytes`, or copying it otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytesstr::BytesStr;
+    ///
+    /// let bytes_str = BytesStr::from_static("Test!");
+    ///
+    /// let mut bytes_str_mut = bytes_str.into_mut();
+    /// bytes_str_mut.push_str(" More!");
+    ///
+    /// assert_eq!(bytes_str_mut.freeze(), "Test! More!");
+    /// ```
+    #[inline]
+    pub fn into_mut(self) -> BytesStrMut {
+        let bytes = match self.bytes.try_into_mut() {
+            Ok(bytes) => bytes,
+            Err(bytes) => BytesMut::from(&bytes[..]),
+        };
+
+        BytesStrMut { bytes }
+    }
+}
+
+impl PartialEq<[u8]> for BytesStrMut {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.bytes.eq(other)
+    }
+}
+
+impl PartialEq<str> for BytesStrMut {
+    fn eq(&self, other: &str) -> bool {
+        self.bytes.eq(other.as_bytes())
+    }
+}
+
+impl PartialEq<&str> for BytesStrMut {
+    fn eq(&self, other: &&str) -> bool {
+        self.bytes.eq(other.as_bytes())
+    }
+}
+
+impl Deref for BytesStrMut {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for BytesStrMut {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<[u8]> for BytesStrMut {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl From<&str> for BytesStrMut {
+    fn from(s: &str) -> Self {
+        let mut this = Self::with_capacity(s.len());
+        this.push_str(s);
+        this
+    }
+}
+
+impl From<String> for BytesStrMut {
+    fn from(s: String) -> Self {
+        Self {
+            bytes: BytesMut::from(s.as_bytes()),
+        }
+    }
+}
+
+impl From<BytesStrMut> for Bytes {
+    fn from(s: BytesStrMut) -> Self {
+        s.bytes.freeze()
+    }
+}
+
+impl fmt::Display for BytesStrMut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+impl fmt::Debug for BytesStrMut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
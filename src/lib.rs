@@ -1,19 +1,106 @@
 //! `BytesStr` is an immutable reference counted UTF8-String
 //! useful for storing views into UTF8-encoded parts of data.
 
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
+use pattern::Pattern;
+use std::char::decode_utf16;
 use std::fmt;
 use std::ops::Deref;
 use std::str::{from_utf8, from_utf8_unchecked, Utf8Error};
 
+mod bytes_str_mut;
+mod pattern;
+#[cfg(feature = "percent")]
+mod percent;
 #[cfg(feature = "serde")]
 mod serde;
 
-/// BytesStr is an immutable UTF8-String using [Bytes] as its buffer.
+pub use bytes_str_mut::BytesStrMut;
+#[cfg(feature = "percent")]
+pub use percent::PercentDecodeError;
+
+/// Marker trait for byte storage that is safe to wrap in a [`BytesStr`] without an `unsafe`
+/// constructor.
+///
+/// Implementors must guarantee that the bytes returned by [`AsRef::as_ref`] cannot change
+/// through a shared reference, so that a UTF8 check performed once at construction stays valid
+/// for the lifetime of the `BytesStr`. This is implemented for the storages `BytesStr` ships
+/// with; storage types that don't (or can't) uphold this guarantee can still be wrapped with
+/// [`BytesStr::from_utf8_bytes_unchecked`].
+pub trait StableAsRef: AsRef<[u8]> {}
+
+impl StableAsRef for Bytes {}
+impl StableAsRef for BytesMut {}
+impl StableAsRef for Vec<u8> {}
+impl<const N: usize> StableAsRef for [u8; N] {}
+impl StableAsRef for &'static [u8] {}
+
+/// BytesStr is an immutable UTF8-String using `B` (by default [Bytes]) as its buffer.
 #[derive(Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct BytesStr {
+pub struct BytesStr<B = Bytes> {
     // This must always be valid UTF8
-    bytes: Bytes,
+    bytes: B,
+}
+
+impl<B: StableAsRef> BytesStr<B> {
+    /// Try to create a BytesStr from a byte storage `B`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use bytesstr::BytesStr;
+    ///
+    /// let buffer = Bytes::from_static(b"Test!");
+    ///
+    /// let bytes_str = BytesStr::from_utf8_bytes(buffer).unwrap();
+    ///
+    /// assert_eq!(bytes_str, "Test!");
+    /// ```
+    #[inline]
+    pub fn from_utf8_bytes(bytes: B) -> Result<Self, Utf8Error> {
+        from_utf8(bytes.as_ref())?;
+        Ok(Self { bytes })
+    }
+}
+
+impl<B: AsRef<[u8]>> BytesStr<B> {
+    /// Create a `BytesStr` from a byte storage `B` which contains valid UTF8 and doesn't need
+    /// to be checked.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use bytesstr::BytesStr;
+    ///
+    /// let buffer = Bytes::from_static(b"Test!");
+    ///
+    /// let bytes_str = unsafe { BytesStr::from_utf8_bytes_unchecked(buffer) };
+    ///
+    /// assert_eq!(bytes_str, "Test!");
+    /// ```
+    /// # Safety
+    ///
+    /// Passed `bytes` parameter must be valid UTF8
+    ///
+    /// # Panics
+    ///
+    /// If `debug-assertions` are enabled an UTF8 check is performed, which panics on error.
+    #[inline]
+    pub unsafe fn from_utf8_bytes_unchecked(bytes: B) -> Self {
+        debug_assert!(from_utf8(bytes.as_ref()).is_ok());
+
+        Self { bytes }
+    }
+
+    /// Returns a str slice into the internal buffer
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        // Safety:
+        // There is no safe way to construct a BytesStr from an invalid UTF8 string
+        unsafe { from_utf8_unchecked(self.bytes.as_ref()) }
+    }
 }
 
 impl BytesStr {
@@ -62,7 +149,11 @@ impl BytesStr {
         }
     }
 
-    /// Try to create a BytesStr from an Bytes buffer.
+    /// Create a `BytesStr` from a `Bytes` buffer, replacing any invalid UTF8 sequences with
+    /// `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// If `bytes` is already valid UTF8 this is a zero-copy operation, reusing the original
+    /// buffer instead of allocating a new one.
     ///
     /// # Example
     ///
@@ -70,52 +161,102 @@ impl BytesStr {
     /// use bytes::Bytes;
     /// use bytesstr::BytesStr;
     ///
-    /// let buffer = Bytes::from_static(b"Test!");
+    /// let buffer = Bytes::from_static(b"Hello \xF0\x90\x80World!");
     ///
-    /// let bytes_str = BytesStr::from_utf8_bytes(buffer).unwrap();
+    /// let bytes_str = BytesStr::from_utf8_bytes_lossy(buffer);
     ///
-    /// assert_eq!(bytes_str, "Test!");
+    /// assert_eq!(bytes_str, "Hello \u{FFFD}World!");
     /// ```
-    #[inline]
-    pub fn from_utf8_bytes(bytes: Bytes) -> Result<Self, Utf8Error> {
-        from_utf8(&bytes)?;
-        Ok(Self { bytes })
+    pub fn from_utf8_bytes_lossy(bytes: Bytes) -> Self {
+        let mut remaining = &bytes[..];
+        let mut offset = 0;
+        let mut lossy = String::new();
+
+        loop {
+            match from_utf8(remaining) {
+                Ok(_) => {
+                    if offset == 0 {
+                        // Nothing was invalid, reuse the original buffer
+                        return Self { bytes };
+                    }
+
+                    lossy.push_str(unsafe { from_utf8_unchecked(remaining) });
+                    break;
+                }
+                Err(e) => {
+                    let valid_up_to = e.valid_up_to();
+
+                    lossy.push_str(unsafe { from_utf8_unchecked(&remaining[..valid_up_to]) });
+                    lossy.push('\u{FFFD}');
+
+                    match e.error_len() {
+                        Some(error_len) => {
+                            let advance = valid_up_to + error_len;
+                            remaining = &remaining[advance..];
+                            offset += advance;
+                        }
+                        None => break,
+                    }
+                }
+            }
+        }
+
+        Self {
+            bytes: Bytes::from(lossy.into_bytes()),
+        }
     }
 
-    /// Create a `BytesStr` from a `Bytes` which contains valid UTF8 and doesn't need to be checked.
+    /// Decodes a UTF16-encoded slice `v` into a `BytesStr`.
     ///
     /// # Example
     ///
     /// ```
-    /// use bytes::Bytes;
     /// use bytesstr::BytesStr;
     ///
-    /// let buffer = Bytes::from_static(b"Test!");
-    ///
-    /// let bytes_str = unsafe { BytesStr::from_utf8_bytes_unchecked(buffer) };
+    /// let v = [0x0048, 0x0065, 0x006C, 0x006C, 0x006F];
     ///
-    /// assert_eq!(bytes_str, "Test!");
+    /// assert_eq!(BytesStr::from_utf16(&v).unwrap(), "Hello");
     /// ```
-    /// # Safety
+    pub fn from_utf16(v: &[u16]) -> Result<Self, FromUtf16Error> {
+        let s: String = decode_utf16(v.iter().copied())
+            .collect::<Result<String, _>>()
+            .map_err(|_| FromUtf16Error(()))?;
+
+        // Safety: `String` is always valid UTF8
+        Ok(unsafe { Self::from_utf8_bytes_unchecked(Bytes::from(s.into_bytes())) })
+    }
+
+    /// Decodes a UTF16-encoded slice `v` into a `BytesStr`, replacing unpaired surrogates with
+    /// `U+FFFD REPLACEMENT CHARACTER`.
     ///
-    /// Passed `Bytes` parameter must be valid UTF8
+    /// # Example
     ///
-    /// # Panics
+    /// ```
+    /// use bytesstr::BytesStr;
     ///
-    /// If `debug-assertions` are enabled an UTF8 check is performed, which panics on error.
-    #[inline]
-    pub unsafe fn from_utf8_bytes_unchecked(bytes: Bytes) -> Self {
-        debug_assert!(from_utf8(&bytes).is_ok());
+    /// let v = [0x0048, 0x0065, 0xD800, 0x006C, 0x006C, 0x006F];
+    ///
+    /// assert_eq!(BytesStr::from_utf16_lossy(&v), "He\u{FFFD}llo");
+    /// ```
+    pub fn from_utf16_lossy(v: &[u16]) -> Self {
+        let s: String = decode_utf16(v.iter().copied())
+            .map(|r| r.unwrap_or('\u{FFFD}'))
+            .collect();
 
-        Self { bytes }
+        // Safety: `String` is always valid UTF8
+        unsafe { Self::from_utf8_bytes_unchecked(Bytes::from(s.into_bytes())) }
     }
 
-    /// Returns a str slice into the internal buffer
+    /// Returns a reference to the underlying `Bytes` buffer.
     #[inline]
-    pub fn as_str(&self) -> &str {
-        // Safety:
-        // There is no safe way to construct a BytesStr from an invalid UTF8 string
-        unsafe { from_utf8_unchecked(&self.bytes) }
+    pub fn as_bytes(&self) -> &Bytes {
+        &self.bytes
+    }
+
+    /// Consumes `self`, returning the underlying `Bytes` buffer.
+    #[inline]
+    pub fn into_bytes(self) -> Bytes {
+        self.bytes
     }
 
     /// Pass an subset of the BytesStr to create a new BytesStr containing the `subset` slice
@@ -160,27 +301,117 @@ impl BytesStr {
             bytes: Bytes::copy_from_slice(&self.bytes),
         }
     }
+
+    /// Returns a `BytesStr` with leading and trailing whitespace removed, sharing the buffer
+    /// with `self`.
+    #[inline]
+    pub fn trim(&self) -> Self {
+        self.slice_ref(self.as_str().trim())
+    }
+
+    /// Returns a `BytesStr` with leading whitespace removed, sharing the buffer with `self`.
+    #[inline]
+    pub fn trim_start(&self) -> Self {
+        self.slice_ref(self.as_str().trim_start())
+    }
+
+    /// Returns a `BytesStr` with trailing whitespace removed, sharing the buffer with `self`.
+    #[inline]
+    pub fn trim_end(&self) -> Self {
+        self.slice_ref(self.as_str().trim_end())
+    }
+
+    /// Returns a `BytesStr` with the given prefix removed, sharing the buffer with `self`.
+    ///
+    /// Returns `None` if `self` does not start with `pat`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytesstr::BytesStr;
+    ///
+    /// let bytes_str = BytesStr::from_static("Bearer token123");
+    ///
+    /// assert_eq!(bytes_str.strip_prefix("Bearer ").unwrap(), "token123");
+    /// ```
+    #[inline]
+    pub fn strip_prefix<'a, P>(&'a self, pat: P) -> Option<Self>
+    where
+        P: Pattern<'a>,
+    {
+        pat.strip_prefix_of(self.as_str()).map(|s| self.slice_ref(s))
+    }
+
+    /// Returns a `BytesStr` with the given suffix removed, sharing the buffer with `self`.
+    ///
+    /// Returns `None` if `self` does not end with `pat`.
+    #[inline]
+    pub fn strip_suffix<'a, P>(&'a self, pat: P) -> Option<Self>
+    where
+        P: Pattern<'a>,
+    {
+        pat.strip_suffix_of(self.as_str()).map(|s| self.slice_ref(s))
+    }
+
+    /// Splits `self` on the first occurrence of `pat`, returning both halves as `BytesStr`
+    /// sharing the buffer with `self`.
+    #[inline]
+    pub fn split_once<'a, P>(&'a self, pat: P) -> Option<(Self, Self)>
+    where
+        P: Pattern<'a>,
+    {
+        let (before, after) = pat.split_once_of(self.as_str())?;
+        Some((self.slice_ref(before), self.slice_ref(after)))
+    }
+
+    /// Splits `self` on the last occurrence of `pat`, returning both halves as `BytesStr`
+    /// sharing the buffer with `self`.
+    #[inline]
+    pub fn rsplit_once<'a, P>(&'a self, pat: P) -> Option<(Self, Self)>
+    where
+        P: Pattern<'a>,
+    {
+        let (before, after) = pat.rsplit_once_of(self.as_str())?;
+        Some((self.slice_ref(before), self.slice_ref(after)))
+    }
+
+    /// Returns an iterator over the substrings of `self` separated by `pat`, each a `BytesStr`
+    /// sharing the buffer with `self`.
+    #[inline]
+    pub fn split<'a, P>(&'a self, pat: P) -> impl Iterator<Item = Self> + 'a
+    where
+        P: Pattern<'a>,
+    {
+        pat.split_of(self.as_str()).map(move |s| self.slice_ref(s))
+    }
+
+    /// Returns an iterator over the lines of `self`, each a `BytesStr` sharing the buffer
+    /// with `self`.
+    #[inline]
+    pub fn lines(&self) -> impl Iterator<Item = Self> + '_ {
+        self.as_str().lines().map(move |s| self.slice_ref(s))
+    }
 }
 
-impl PartialEq<[u8]> for BytesStr {
+impl<B: AsRef<[u8]>> PartialEq<[u8]> for BytesStr<B> {
     fn eq(&self, other: &[u8]) -> bool {
-        self.bytes.eq(other)
+        self.bytes.as_ref().eq(other)
     }
 }
 
-impl PartialEq<str> for BytesStr {
+impl<B: AsRef<[u8]>> PartialEq<str> for BytesStr<B> {
     fn eq(&self, other: &str) -> bool {
-        self.bytes.eq(other.as_bytes())
+        self.bytes.as_ref().eq(other.as_bytes())
     }
 }
 
-impl PartialEq<&str> for BytesStr {
+impl<B: AsRef<[u8]>> PartialEq<&str> for BytesStr<B> {
     fn eq(&self, other: &&str) -> bool {
-        self.bytes.eq(other.as_bytes())
+        self.bytes.as_ref().eq(other.as_bytes())
     }
 }
 
-impl Deref for BytesStr {
+impl<B: AsRef<[u8]>> Deref for BytesStr<B> {
     type Target = str;
 
     fn deref(&self) -> &Self::Target {
@@ -188,15 +419,15 @@ impl Deref for BytesStr {
     }
 }
 
-impl AsRef<str> for BytesStr {
+impl<B: AsRef<[u8]>> AsRef<str> for BytesStr<B> {
     fn as_ref(&self) -> &str {
         self.as_str()
     }
 }
 
-impl AsRef<[u8]> for BytesStr {
+impl<B: AsRef<[u8]>> AsRef<[u8]> for BytesStr<B> {
     fn as_ref(&self) -> &[u8] {
-        &self.bytes
+        self.bytes.as_ref()
     }
 }
 
@@ -222,14 +453,40 @@ impl From<String> for BytesStr {
     }
 }
 
-impl fmt::Display for BytesStr {
+impl From<BytesStr> for Bytes {
+    fn from(s: BytesStr) -> Self {
+        s.bytes
+    }
+}
+
+impl TryFrom<Bytes> for BytesStr {
+    type Error = Utf8Error;
+
+    fn try_from(bytes: Bytes) -> Result<Self, Self::Error> {
+        Self::from_utf8_bytes(bytes)
+    }
+}
+
+impl<B: AsRef<[u8]>> fmt::Display for BytesStr<B> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.as_str().fmt(f)
     }
 }
 
-impl fmt::Debug for BytesStr {
+impl<B: AsRef<[u8]>> fmt::Debug for BytesStr<B> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.as_str().fmt(f)
     }
 }
+
+/// An error returned by [`BytesStr::from_utf16`] when the input is not valid UTF16.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FromUtf16Error(());
+
+impl fmt::Display for FromUtf16Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        "invalid utf-16: lone surrogate found".fmt(f)
+    }
+}
+
+impl std::error::Error for FromUtf16Error {}
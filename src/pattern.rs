@@ -0,0 +1,88 @@
+//! A small, stable-Rust substitute for `std::str::pattern::Pattern`.
+//!
+//! The real `Pattern` trait is unstable, so [`BytesStr`](crate::BytesStr)'s splitting and
+//! prefix/suffix stripping methods are generic over this local trait instead, implemented for the same
+//! `char`, `&str` and `&[char]` patterns accepted by `str` itself.
+
+/// A pattern accepted by [`BytesStr`](crate::BytesStr)'s zero-copy substring methods.
+pub trait Pattern<'a>: sealed::Sealed<'a> {}
+
+impl<'a> Pattern<'a> for char {}
+impl<'a> Pattern<'a> for &'a str {}
+impl<'a> Pattern<'a> for &'a [char] {}
+
+mod sealed {
+    pub trait Sealed<'a> {
+        fn strip_prefix_of(self, s: &'a str) -> Option<&'a str>;
+        fn strip_suffix_of(self, s: &'a str) -> Option<&'a str>;
+        fn split_once_of(self, s: &'a str) -> Option<(&'a str, &'a str)>;
+        fn rsplit_once_of(self, s: &'a str) -> Option<(&'a str, &'a str)>;
+        fn split_of(self, s: &'a str) -> Box<dyn Iterator<Item = &'a str> + 'a>;
+    }
+
+    impl<'a> Sealed<'a> for char {
+        fn strip_prefix_of(self, s: &'a str) -> Option<&'a str> {
+            s.strip_prefix(self)
+        }
+
+        fn strip_suffix_of(self, s: &'a str) -> Option<&'a str> {
+            s.strip_suffix(self)
+        }
+
+        fn split_once_of(self, s: &'a str) -> Option<(&'a str, &'a str)> {
+            s.split_once(self)
+        }
+
+        fn rsplit_once_of(self, s: &'a str) -> Option<(&'a str, &'a str)> {
+            s.rsplit_once(self)
+        }
+
+        fn split_of(self, s: &'a str) -> Box<dyn Iterator<Item = &'a str> + 'a> {
+            Box::new(s.split(self))
+        }
+    }
+
+    impl<'a> Sealed<'a> for &'a str {
+        fn strip_prefix_of(self, s: &'a str) -> Option<&'a str> {
+            s.strip_prefix(self)
+        }
+
+        fn strip_suffix_of(self, s: &'a str) -> Option<&'a str> {
+            s.strip_suffix(self)
+        }
+
+        fn split_once_of(self, s: &'a str) -> Option<(&'a str, &'a str)> {
+            s.split_once(self)
+        }
+
+        fn rsplit_once_of(self, s: &'a str) -> Option<(&'a str, &'a str)> {
+            s.rsplit_once(self)
+        }
+
+        fn split_of(self, s: &'a str) -> Box<dyn Iterator<Item = &'a str> + 'a> {
+            Box::new(s.split(self))
+        }
+    }
+
+    impl<'a> Sealed<'a> for &'a [char] {
+        fn strip_prefix_of(self, s: &'a str) -> Option<&'a str> {
+            s.strip_prefix(self)
+        }
+
+        fn strip_suffix_of(self, s: &'a str) -> Option<&'a str> {
+            s.strip_suffix(self)
+        }
+
+        fn split_once_of(self, s: &'a str) -> Option<(&'a str, &'a str)> {
+            s.split_once(self)
+        }
+
+        fn rsplit_once_of(self, s: &'a str) -> Option<(&'a str, &'a str)> {
+            s.rsplit_once(self)
+        }
+
+        fn split_of(self, s: &'a str) -> Box<dyn Iterator<Item = &'a str> + 'a> {
+            Box::new(s.split(self))
+        }
+    }
+}
@@ -0,0 +1,153 @@
+//! RFC 3986 percent-encoding and decoding for [`BytesStr`].
+
+use crate::BytesStr;
+use bytes::{Bytes, BytesMut};
+use std::fmt;
+use std::str::{from_utf8, Utf8Error};
+
+/// Returns `true` if `byte` is part of the unreserved character set
+/// (`A-Z a-z 0-9 - . _ ~`) and therefore never needs to be percent-encoded.
+#[inline]
+fn is_unreserved(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~')
+}
+
+#[inline]
+fn hex_digit(value: u8) -> u8 {
+    match value {
+        0..=9 => b'0' + value,
+        10..=15 => b'A' + (value - 10),
+        _ => unreachable!(),
+    }
+}
+
+#[inline]
+fn hex_value(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        b'A'..=b'F' => Some(byte - b'A' + 10),
+        _ => None,
+    }
+}
+
+impl BytesStr {
+    /// Percent-decodes `self`, returning a new `BytesStr`.
+    ///
+    /// If `self` contains no `%` the original buffer is reused without allocating.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a `%` is not followed by two hexadecimal digits, or if the
+    /// decoded bytes are not valid UTF8.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytesstr::BytesStr;
+    ///
+    /// let encoded = BytesStr::from_static("Hello%20World%21");
+    ///
+    /// assert_eq!(encoded.percent_decode().unwrap(), "Hello World!");
+    /// ```
+    pub fn percent_decode(&self) -> Result<Self, PercentDecodeError> {
+        let bytes = self.bytes.as_ref();
+
+        if !bytes.contains(&b'%') {
+            return Ok(Self {
+                bytes: self.bytes.clone(),
+            });
+        }
+
+        let mut decoded = BytesMut::with_capacity(bytes.len());
+        let mut iter = bytes.iter().copied().enumerate();
+
+        while let Some((i, byte)) = iter.next() {
+            if byte != b'%' {
+                decoded.extend_from_slice(&[byte]);
+                continue;
+            }
+
+            match (bytes.get(i + 1).copied(), bytes.get(i + 2).copied()) {
+                (Some(hi), Some(lo)) if hex_value(hi).is_some() && hex_value(lo).is_some() => {
+                    let value = (hex_value(hi).unwrap() << 4) | hex_value(lo).unwrap();
+                    decoded.extend_from_slice(&[value]);
+                    iter.next();
+                    iter.next();
+                }
+                _ => return Err(PercentDecodeError::MalformedTriplet),
+            }
+        }
+
+        let bytes: Bytes = decoded.freeze();
+
+        from_utf8(&bytes).map_err(PercentDecodeError::InvalidUtf8)?;
+
+        Ok(Self { bytes })
+    }
+
+    /// Percent-encodes `self`, passing through the unreserved set
+    /// (`A-Z a-z 0-9 - . _ ~`) verbatim and emitting `%XX` (uppercase hex) for every other byte.
+    ///
+    /// If nothing needs escaping, `self` is cloned without allocating.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytesstr::BytesStr;
+    ///
+    /// let decoded = BytesStr::from_static("Hello World!");
+    ///
+    /// assert_eq!(decoded.percent_encode(), "Hello%20World%21");
+    /// ```
+    pub fn percent_encode(&self) -> Self {
+        let bytes = self.bytes.as_ref();
+
+        if bytes.iter().copied().all(is_unreserved) {
+            return self.clone();
+        }
+
+        let mut encoded = BytesMut::with_capacity(bytes.len());
+
+        for &byte in bytes {
+            if is_unreserved(byte) {
+                encoded.extend_from_slice(&[byte]);
+            } else {
+                encoded.extend_from_slice(&[b'%', hex_digit(byte >> 4), hex_digit(byte & 0xF)]);
+            }
+        }
+
+        // Every output byte is an unreserved ASCII byte, `%` or an uppercase hex digit,
+        // so the result is pure ASCII and therefore valid UTF8.
+        Self {
+            bytes: encoded.freeze(),
+        }
+    }
+}
+
+/// An error returned by [`BytesStr::percent_decode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PercentDecodeError {
+    /// A `%` was not followed by two hexadecimal digits.
+    MalformedTriplet,
+    /// The decoded bytes are not valid UTF8.
+    InvalidUtf8(Utf8Error),
+}
+
+impl fmt::Display for PercentDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MalformedTriplet => "malformed percent-encoded triplet".fmt(f),
+            Self::InvalidUtf8(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for PercentDecodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::MalformedTriplet => None,
+            Self::InvalidUtf8(e) => Some(e),
+        }
+    }
+}